@@ -1,36 +1,197 @@
+use clap::Parser;
 use regex::Regex;
 use serde_json::Value;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::{self, read_dir},
     path::Path,
     process::{exit, Command},
 };
 
+/// CLI options for laravel-env-diff.
+#[derive(Parser, Debug)]
+#[command(author, version, about = "Diff a Laravel project's .env against its cached config", long_about = None)]
+struct Opts {
+    /// .env file to load, in precedence order (later files override earlier ones).
+    /// Repeat to layer multiple files, e.g. `--env .env --env .env.testing`. Defaults
+    /// to `.env`, then `.env.{APP_ENV}` when the `APP_ENV` environment variable is set
+    #[arg(long = "env")]
+    env_files: Vec<String>,
+
+    /// Directory containing Laravel's config/*.php files
+    #[arg(long = "config-dir", default_value = "config")]
+    config_dir: String,
+
+    /// Path to the cached bootstrap/cache/config.php file
+    #[arg(long, default_value = "bootstrap/cache/config.php")]
+    cache: String,
+
+    /// Increase verbosity (stackable, e.g. -vv); prints full per-key detail
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Decrease verbosity (stackable); suppresses all output but the exit code
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    quiet: u8,
+
+    /// Fail closed: report a key whose precise config path isn't found in the cache
+    /// instead of falling back to section-level matching, and require exact
+    /// (case-sensitive) null matches at that path. Keys with no known config path
+    /// still use the section-level check
+    #[arg(long)]
+    strict: bool,
+
+    /// Parse the config cache by shelling out to `php -r` instead of the native
+    /// parser; only needed when the cache contains closures or objects
+    #[arg(long = "use-php")]
+    use_php: bool,
+
+    /// Run a full bidirectional audit: also report env() keys referenced by config
+    /// files but missing from .env ([UNDEFINED]), and .env keys unused by any
+    /// config file ([UNUSED])
+    #[arg(long)]
+    audit: bool,
+
+    /// Output format for the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+}
+
+#[derive(Copy, Clone, Debug, clap::ValueEnum)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
+/// The kind of drift a `Finding` represents.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum FindingKind {
+    Diff,
+    Missing,
+    Undefined,
+    Unused,
+    PathUnresolved,
+}
+
+/// One piece of drift between `.env` and the config cache, shared by the text and
+/// JSON renderers so both report exactly the same findings.
+#[derive(Clone, Debug, serde::Serialize)]
+struct Finding {
+    key: String,
+    kind: FindingKind,
+    env_value: Option<String>,
+    config_value: Option<Value>,
+    config_path: Option<String>,
+    source_file: Option<String>,
+}
+
 fn main() {
-    // 1. Parse Keys and values directly from .env file
-    let env_vars = load_env_file_keys_values(".env");
-    
-    // 2. Create env('XXX') key → config section (filename) map from config/*.php
-    let config_dir = Path::new("config");
-    let env_key_to_section = parse_config_env_keys(config_dir);
-
-    // 3. Load bootstrap/cache/config.php with PHP and convert to JSON
-    let config_cache_path = "bootstrap/cache/config.php";
-    if !Path::new(config_cache_path).exists() {
-        println!("Config cache file not found: {config_cache_path}");
+    let opts = Opts::parse();
+    let verbosity = opts.verbose as i16 - opts.quiet as i16;
+
+    // 1. Parse keys and values from the layered .env file(s), later files overriding earlier
+    let env_vars = load_env_file_keys_values(&resolve_env_files(&opts.env_files));
+
+    // 2. Create env('XXX') key → config section/path maps from config/*.php
+    let config_dir = Path::new(&opts.config_dir);
+    let (env_key_to_section, env_key_to_path) = parse_config_env_keys(config_dir);
+
+    // 3. Load the cached config and convert to JSON
+    if !Path::new(&opts.cache).exists() {
+        eprintln!("Config cache file not found: {}", opts.cache);
         exit(1);
     }
-    
-    let config_json = load_config_php_as_json(config_cache_path);
-    
-    println!("=== Differences between .env and config cache ===");
 
-    let mut found_diff = false;
+    let config_json = if opts.use_php {
+        load_config_via_php(&opts.cache)
+    } else {
+        load_config_native(&opts.cache)
+    };
+
+    // 4. Check differences starting from .env, preferring the exact dotted path
+    let mut findings = diff_findings(
+        &env_vars,
+        &env_key_to_section,
+        &env_key_to_path,
+        &config_json,
+        opts.strict,
+    );
+
+    // 5. In audit mode, also flag env() keys with no .env entry, and .env keys no
+    // config file reads.
+    if opts.audit {
+        findings.extend(audit_findings(&env_key_to_section, &env_vars));
+    }
+
+    match opts.format {
+        ReportFormat::Json => render_json_report(&findings, verbosity),
+        ReportFormat::Text => render_text_report(&findings, verbosity),
+    }
+
+    if !findings.is_empty() {
+        exit(2);
+    }
+}
+
+/// Compare each `.env` key against the cached config, preferring the exact dotted
+/// path and falling back to a section-level check when no path is known. Under
+/// `--strict`, a path that can't be resolved is itself reported as drift instead of
+/// silently falling back, and null comparisons at a resolved path require an exact
+/// (case-sensitive) match; keys with no known path still get the section-level check.
+fn diff_findings(
+    env_vars: &HashMap<String, String>,
+    env_key_to_section: &HashMap<String, String>,
+    env_key_to_path: &HashMap<String, String>,
+    config_json: &Value,
+    strict: bool,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    for (env_key, env_val) in env_vars {
+        if let Some(path) = env_key_to_path.get(env_key) {
+            let source_file = path.split('.').next().map(|stem| format!("config/{stem}.php"));
+
+            if let Some(resolved) = resolve_config_path(config_json, path) {
+                let resolved_str = json_value_to_string(resolved);
+
+                let matches = if env_val.to_lowercase() == "null" && !strict {
+                    resolved_str.to_lowercase() == "null"
+                } else {
+                    resolved_str == *env_val
+                };
+
+                if !matches {
+                    findings.push(Finding {
+                        key: env_key.clone(),
+                        kind: FindingKind::Diff,
+                        env_value: Some(env_val.clone()),
+                        config_value: Some(resolved.clone()),
+                        config_path: Some(path.clone()),
+                        source_file,
+                    });
+                }
+                continue;
+            }
+
+            if strict {
+                findings.push(Finding {
+                    key: env_key.clone(),
+                    kind: FindingKind::PathUnresolved,
+                    env_value: Some(env_val.clone()),
+                    config_value: None,
+                    config_path: Some(path.clone()),
+                    source_file,
+                });
+                continue;
+            }
+            // Path couldn't be resolved (e.g. cache predates the config file) — fall
+            // back to the section-level check below.
+        }
 
-    // 4. Check differences starting from .env
-    for (env_key, env_val) in &env_vars {
         if let Some(section) = env_key_to_section.get(env_key) {
+            let source_file = Some(format!("config/{section}.php"));
+
             if let Some(section_val) = config_json.get(section) {
                 let section_str = json_value_to_string(section_val);
 
@@ -42,51 +203,207 @@ fn main() {
                 };
 
                 if !matches {
-                    println!("[DIFF] {env_key}");
-                    found_diff = true;
+                    findings.push(Finding {
+                        key: env_key.clone(),
+                        kind: FindingKind::Diff,
+                        env_value: Some(env_val.clone()),
+                        config_value: Some(section_val.clone()),
+                        config_path: None,
+                        source_file,
+                    });
                 }
             } else {
-                println!("[MISSING] Section '{section}' not found in config.php");
-                found_diff = true;
+                findings.push(Finding {
+                    key: env_key.clone(),
+                    kind: FindingKind::Missing,
+                    env_value: Some(env_val.clone()),
+                    config_value: None,
+                    config_path: None,
+                    source_file,
+                });
             }
         }
         // Ignore keys not in env_key_to_section (no warning)
     }
 
-    if !found_diff {
-        println!("No differences found between .env and config cache.");
+    findings
+}
+
+/// Build `--audit` findings: `env()` keys referenced by config files but missing
+/// from `.env` ([UNDEFINED]), and `.env` keys unused by any config file ([UNUSED]).
+fn audit_findings(
+    env_key_to_section: &HashMap<String, String>,
+    env_vars: &HashMap<String, String>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let config_env_keys: HashSet<String> = env_key_to_section.keys().cloned().collect();
+    let env_var_keys: HashSet<String> = env_vars.keys().cloned().collect();
+
+    let mut undefined: Vec<String> = config_env_keys.difference(&env_var_keys).cloned().collect();
+    undefined.sort();
+    for key in undefined {
+        let source_file = env_key_to_section.get(&key).map(|section| format!("config/{section}.php"));
+        findings.push(Finding {
+            key,
+            kind: FindingKind::Undefined,
+            env_value: None,
+            config_value: None,
+            config_path: None,
+            source_file,
+        });
+    }
+
+    let mut unused: Vec<String> = env_var_keys.difference(&config_env_keys).cloned().collect();
+    unused.sort();
+    for key in unused {
+        let env_value = env_vars.get(&key).cloned();
+        findings.push(Finding {
+            key,
+            kind: FindingKind::Unused,
+            env_value,
+            config_value: None,
+            config_path: None,
+            source_file: None,
+        });
     }
+
+    findings
 }
 
-/// Parse keys and values from .env file into HashMap
-fn load_env_file_keys_values(path: &str) -> HashMap<String, String> {
-    let content = fs::read_to_string(path).expect("Failed to read .env file");
-    let mut map = HashMap::new();
+/// Render findings the way a human reads them: full per-key detail at `-v`, just a
+/// summary line at the default verbosity, nothing at `-q`.
+fn render_text_report(findings: &[Finding], verbosity: i16) {
+    if verbosity >= 1 {
+        println!("=== Differences between .env and config cache ===");
+        for finding in findings {
+            println!("{}", format_text_finding(finding));
+        }
+    }
 
-    let re = Regex::new(r#"^\s*([A-Z0-9_]+)\s*=\s*(.*)\s*$"#).unwrap();
+    if verbosity >= 0 {
+        if findings.is_empty() {
+            println!("No differences found between .env and config cache.");
+        } else {
+            let count = |kind: FindingKind| findings.iter().filter(|f| f.kind == kind).count();
+            println!(
+                "Found {} difference(s), {} missing section(s), {} undefined key(s), {} unused key(s).",
+                count(FindingKind::Diff) + count(FindingKind::PathUnresolved),
+                count(FindingKind::Missing),
+                count(FindingKind::Undefined),
+                count(FindingKind::Unused),
+            );
+        }
+    }
+}
 
-    for line in content.lines() {
-        if let Some(cap) = re.captures(line) {
-            let key = cap[1].to_string();
-            let mut val = cap[2].trim().to_string();
+/// Format a single finding as the classic `[DIFF]`/`[MISSING]`/`[UNDEFINED]`/`[UNUSED]` line.
+fn format_text_finding(finding: &Finding) -> String {
+    match finding.kind {
+        FindingKind::Diff => match &finding.config_path {
+            Some(path) => format!("[DIFF] {} (config path: {path})", finding.key),
+            None => format!("[DIFF] {}", finding.key),
+        },
+        FindingKind::Missing => {
+            let section = finding
+                .source_file
+                .as_deref()
+                .and_then(|f| f.strip_prefix("config/"))
+                .and_then(|f| f.strip_suffix(".php"))
+                .unwrap_or("?");
+            format!("[MISSING] Section '{section}' not found in config.php")
+        }
+        FindingKind::Undefined => format!("[UNDEFINED] {}", finding.key),
+        FindingKind::Unused => format!("[UNUSED] {}", finding.key),
+        FindingKind::PathUnresolved => {
+            let path = finding.config_path.as_deref().unwrap_or("?");
+            format!("[DIFF] {} (config path '{path}' not found in cache)", finding.key)
+        }
+    }
+}
+
+/// Render the complete finding set as structured JSON for CI jobs and editor plugins.
+/// Respects `-q` the same way `render_text_report` does (suppresses all output but the
+/// exit code); unlike the text report, it isn't tiered further by `-v` since the JSON
+/// always carries full per-key detail.
+fn render_json_report(findings: &[Finding], verbosity: i16) {
+    if verbosity < 0 {
+        return;
+    }
+
+    let report = build_json_report(findings);
+    println!("{}", serde_json::to_string_pretty(&report).expect("Failed to serialize report"));
+}
+
+/// Build the JSON report value from findings. `Finding` derives `Serialize` directly,
+/// so `config_value` (a real `serde_json::Value`) comes through as nested JSON rather
+/// than a Rust debug-formatted string.
+fn build_json_report(findings: &[Finding]) -> Value {
+    serde_json::json!({
+        "summary": { "count": findings.len() },
+        "findings": findings,
+    })
+}
+
+/// Build the ordered list of env files to load. If the user didn't pass `--env`
+/// explicitly, default to `.env`, then layer `.env.{APP_ENV}` on top when the
+/// `APP_ENV` environment variable is set — matching Laravel's own precedence.
+fn resolve_env_files(explicit: &[String]) -> Vec<(String, bool)> {
+    if !explicit.is_empty() {
+        return explicit.iter().map(|path| (path.clone(), true)).collect();
+    }
 
-            // Remove quotes
-            if (val.starts_with('"') && val.ends_with('"'))
-                || (val.starts_with('\'') && val.ends_with('\''))
-            {
-                val = val[1..val.len() - 1].to_string();
+    let mut files = vec![(".env".to_string(), true)];
+    if let Ok(app_env) = std::env::var("APP_ENV") {
+        if !app_env.is_empty() {
+            files.push((format!(".env.{app_env}"), false));
+        }
+    }
+    files
+}
+
+/// Parse keys and values from an ordered list of `.env` files into one HashMap,
+/// later files overriding earlier ones. Each entry is `(path, required)` — a
+/// missing optional file (e.g. an absent `.env.{APP_ENV}`) is skipped silently.
+fn load_env_file_keys_values(files: &[(String, bool)]) -> HashMap<String, String> {
+    let re = Regex::new(r#"^\s*([A-Z0-9_]+)\s*=\s*(.*)\s*$"#).unwrap();
+    let mut map = HashMap::new();
+
+    for (path, required) in files {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(_) if !required => continue,
+            Err(err) => {
+                eprintln!("Failed to read env file {path}: {err}");
+                exit(1);
             }
+        };
+
+        for line in content.lines() {
+            if let Some(cap) = re.captures(line) {
+                let key = cap[1].to_string();
+                let mut val = cap[2].trim().to_string();
 
-            map.insert(key, val);
+                // Remove quotes
+                if (val.starts_with('"') && val.ends_with('"'))
+                    || (val.starts_with('\'') && val.ends_with('\''))
+                {
+                    val = val[1..val.len() - 1].to_string();
+                }
+
+                map.insert(key, val);
+            }
         }
     }
     map
 }
 
-/// Return env('XXX') call key → config section (filename) map from config/*.php
-fn parse_config_env_keys(config_dir: &Path) -> HashMap<String, String> {
-    let mut map = HashMap::new();
-    let re = Regex::new(r#"env\(\s*['"]([A-Z0-9_]+)['"]\s*,?\s*[^)]*\)"#).unwrap();
+/// Return env('XXX') call key → config section (filename) map, and a more precise
+/// key → dotted config path map (e.g. `database.connections.mysql.host`), from config/*.php.
+fn parse_config_env_keys(config_dir: &Path) -> (HashMap<String, String>, HashMap<String, String>) {
+    let mut section_map = HashMap::new();
+    let mut path_map = HashMap::new();
+    let section_re = Regex::new(r#"env\(\s*['"]([A-Z0-9_]+)['"]\s*,?\s*[^)]*\)"#).unwrap();
 
     for entry in read_dir(config_dir).expect("Failed to read config directory") {
         let entry = entry.expect("Failed to read entry");
@@ -96,18 +413,647 @@ fn parse_config_env_keys(config_dir: &Path) -> HashMap<String, String> {
             let file_stem = path.file_stem().unwrap().to_string_lossy().to_string();
             let content = fs::read_to_string(&path).expect("Failed to read config file");
 
-            for cap in re.captures_iter(&content) {
+            for cap in section_re.captures_iter(&content) {
                 let env_key = cap[1].to_string();
-                map.entry(env_key).or_insert_with(|| file_stem.clone());
+                section_map.entry(env_key).or_insert_with(|| file_stem.clone());
+            }
+
+            for (env_key, path) in walk_config_env_paths(&content, &file_stem) {
+                path_map.entry(env_key).or_insert(path);
             }
         }
     }
 
-    map
+    (section_map, path_map)
+}
+
+/// Skip whitespace, `//`/`#` line comments, and `/* ... */` block comments.
+fn skip_ws_and_comments(chars: &[char], pos: &mut usize) {
+    loop {
+        while matches!(chars.get(*pos), Some(c) if c.is_whitespace()) {
+            *pos += 1;
+        }
+        if chars.get(*pos) == Some(&'/') && chars.get(*pos + 1) == Some(&'/') {
+            while !matches!(chars.get(*pos), None | Some('\n')) {
+                *pos += 1;
+            }
+            continue;
+        }
+        if chars.get(*pos) == Some(&'#') {
+            while !matches!(chars.get(*pos), None | Some('\n')) {
+                *pos += 1;
+            }
+            continue;
+        }
+        if chars.get(*pos) == Some(&'/') && chars.get(*pos + 1) == Some(&'*') {
+            *pos += 2;
+            while *pos < chars.len() && !(chars.get(*pos) == Some(&'*') && chars.get(*pos + 1) == Some(&'/')) {
+                *pos += 1;
+            }
+            *pos = (*pos + 2).min(chars.len());
+            continue;
+        }
+        break;
+    }
+}
+
+/// Parse a single- or double-quoted PHP string literal starting at `chars[*pos]`,
+/// advancing `pos` past the closing quote.
+fn parse_quoted_string(chars: &[char], pos: &mut usize) -> String {
+    match chars.get(*pos) {
+        Some('"') => parse_double_quoted(chars, pos),
+        _ => parse_single_quoted(chars, pos),
+    }
+}
+
+fn parse_single_quoted(chars: &[char], pos: &mut usize) -> String {
+    *pos += 1;
+    let mut s = String::new();
+    while let Some(&c) = chars.get(*pos) {
+        if c == '\\' && matches!(chars.get(*pos + 1), Some('\'') | Some('\\')) {
+            s.push(chars[*pos + 1]);
+            *pos += 2;
+            continue;
+        }
+        *pos += 1;
+        if c == '\'' {
+            break;
+        }
+        s.push(c);
+    }
+    s
+}
+
+fn parse_double_quoted(chars: &[char], pos: &mut usize) -> String {
+    *pos += 1;
+    let mut s = String::new();
+    while let Some(&c) = chars.get(*pos) {
+        if c == '\\' {
+            if let Some(escaped) = chars.get(*pos + 1).and_then(|next| match next {
+                'n' => Some('\n'),
+                't' => Some('\t'),
+                'r' => Some('\r'),
+                '"' => Some('"'),
+                '\\' => Some('\\'),
+                '$' => Some('$'),
+                _ => None,
+            }) {
+                s.push(escaped);
+                *pos += 2;
+                continue;
+            }
+        }
+        *pos += 1;
+        if c == '"' {
+            break;
+        }
+        s.push(c);
+    }
+    s
+}
+
+/// Walk a config file's `return [ ... ]` expression, tracking a stack of enclosing
+/// array keys, to resolve each `env('KEY', ...)` call to the dotted path of the key
+/// that holds it (e.g. `'host' => env('DB_HOST', ...)` nested under
+/// `connections.mysql` becomes `database.connections.mysql.host`).
+///
+/// Unlike a flat bracket-counting regex, this recurses into each value's own extent
+/// (strings, nested arrays, function-call argument lists) before looking for the next
+/// sibling, so a `]` or `)` that belongs to an `env()` default value (e.g.
+/// `env('DB_OPTIONS', ['timeout' => 5])`) never gets mistaken for the key-array's own
+/// closing bracket.
+fn walk_config_env_paths(content: &str, file_stem: &str) -> Vec<(String, String)> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut pos = find_return_array_start(content, &chars);
+    let mut walker = ConfigEnvPathWalker { stack: vec![file_stem.to_string()], paths: Vec::new() };
+
+    if pos < chars.len() {
+        walker.walk_value(&chars, &mut pos, None);
+    }
+
+    walker.paths
+}
+
+/// Find the index of the `return` keyword's array/argument-list value, skipping any
+/// earlier occurrences of the word "return" that aren't actually followed by one
+/// (e.g. inside a `/** @return array */` doc comment). `chars` must be `content`'s
+/// chars, already collected by the caller.
+fn find_return_array_start(content: &str, chars: &[char]) -> usize {
+    let return_re = Regex::new(r"\breturn\b").unwrap();
+
+    for m in return_re.find_iter(content) {
+        let mut pos = content[..m.end()].chars().count();
+        skip_ws_and_comments(chars, &mut pos);
+        if looks_like_array_value_start(chars, pos) {
+            return pos;
+        }
+    }
+
+    chars.len()
+}
+
+/// Whether `chars[pos]` looks like the start of an array value: `[`, `(`, or the
+/// legacy `array(` keyword.
+fn looks_like_array_value_start(chars: &[char], pos: usize) -> bool {
+    if matches!(chars.get(pos), Some('[') | Some('(')) {
+        return true;
+    }
+    if !matches_keyword(chars, pos, "array") {
+        return false;
+    }
+    let mut after = pos + "array".len();
+    skip_ws_and_comments(chars, &mut after);
+    chars.get(after) == Some(&'(')
+}
+
+/// Accumulates `env()` key → dotted path pairs while walking a config file's value tree.
+struct ConfigEnvPathWalker {
+    stack: Vec<String>,
+    paths: Vec<(String, String)>,
+}
+
+impl ConfigEnvPathWalker {
+    /// Walk one value expression starting at `chars[*pos]`, advancing `pos` past it.
+    /// `key` is the enclosing `'key' =>` this value was assigned to, if any.
+    fn walk_value(&mut self, chars: &[char], pos: &mut usize, key: Option<&str>) {
+        skip_ws_and_comments(chars, pos);
+
+        match chars.get(*pos) {
+            Some('[') => self.walk_array(chars, pos, '[', ']', key),
+            Some(c) if is_ident_start(*c) && matches_keyword(chars, *pos, "array") => {
+                *pos += "array".len();
+                skip_ws_and_comments(chars, pos);
+                if chars.get(*pos) == Some(&'(') {
+                    self.walk_array(chars, pos, '(', ')', key);
+                }
+            }
+            Some(c) if is_ident_start(*c) && matches_keyword(chars, *pos, "env") => {
+                *pos += "env".len();
+                skip_ws_and_comments(chars, pos);
+                self.walk_env_call(chars, pos, key);
+            }
+            Some('\'') | Some('"') => {
+                parse_quoted_string(chars, pos);
+            }
+            Some(c) if is_ident_start(*c) => {
+                skip_identifier(chars, pos);
+                skip_ws_and_comments(chars, pos);
+                if chars.get(*pos) == Some(&'(') {
+                    skip_balanced(chars, pos, '(', ')');
+                }
+            }
+            _ => skip_bare_token(chars, pos),
+        }
+    }
+
+    /// Walk `'key' => value, ...` (or bare positional values) between `open`/`close`,
+    /// pushing `key` onto the path stack for the duration of this array when present.
+    fn walk_array(&mut self, chars: &[char], pos: &mut usize, open: char, close: char, key: Option<&str>) {
+        if chars.get(*pos) != Some(&open) {
+            return;
+        }
+        *pos += 1;
+
+        if let Some(k) = key {
+            self.stack.push(k.to_string());
+        }
+
+        loop {
+            skip_ws_and_comments(chars, pos);
+            match chars.get(*pos) {
+                Some(c) if *c == close => {
+                    *pos += 1;
+                    break;
+                }
+                None => break,
+                _ => {}
+            }
+
+            match try_consume_entry_key(chars, pos) {
+                Some(string_key) => self.walk_value(chars, pos, string_key.as_deref()),
+                // No `'key' =>` / `BARE_KEY =>` prefix found — parse this as a bare
+                // positional value instead. (A numeric/constant key comes back as
+                // `Some(None)` and is walked by the arm above with no path segment.)
+                None => self.walk_value(chars, pos, None),
+            }
+
+            // A value can be followed by trailing operators before the next `,` —
+            // string concatenation (`.`), ternary/null-coalescing (`?:`, `??`),
+            // arithmetic, etc. Skip each operator and its operand rather than
+            // treating anything but `,`/`close` as the end of this array.
+            loop {
+                skip_ws_and_comments(chars, pos);
+                match chars.get(*pos) {
+                    Some(c) if is_operator_char(*c) => {
+                        skip_operator(chars, pos);
+                        self.walk_value(chars, pos, None);
+                    }
+                    _ => break,
+                }
+            }
+
+            match chars.get(*pos) {
+                Some(',') => {
+                    *pos += 1;
+                }
+                Some(c) if *c == close => {
+                    *pos += 1;
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        if key.is_some() {
+            self.stack.pop();
+        }
+    }
+
+    /// Handle `env('KEY', default)` starting at its `(`: record the path for
+    /// `KEY` (if this call sits under a named key) and skip the rest of the arguments
+    /// without letting anything inside the default value disturb the path stack.
+    fn walk_env_call(&mut self, chars: &[char], pos: &mut usize, key: Option<&str>) {
+        if chars.get(*pos) != Some(&'(') {
+            return;
+        }
+        *pos += 1;
+        skip_ws_and_comments(chars, pos);
+
+        if matches!(chars.get(*pos), Some('\'') | Some('"')) {
+            let env_key = parse_quoted_string(chars, pos);
+            if let Some(k) = key {
+                let mut parts = self.stack.clone();
+                parts.push(k.to_string());
+                self.paths.push((env_key, parts.join(".")));
+            }
+        }
+
+        // Skip the remaining arguments (default value, etc.) up to the matching `)`.
+        skip_balanced_from_depth(chars, pos, '(', ')', 1);
+    }
+}
+
+/// If the array entry at `chars[*pos]` has a `<key> =>` prefix, consume the key and
+/// the `=>` and return the key as a path segment when it's a quoted string — bare
+/// keys (numeric indices, PHP constants) aren't meaningful dotted-path segments, so
+/// they resolve to `Some(None)`, but are still consumed correctly either way.
+/// Returns `None` without consuming anything if there's no `=>` at all, so the
+/// caller can fall back to parsing a bare positional value.
+fn try_consume_entry_key(chars: &[char], pos: &mut usize) -> Option<Option<String>> {
+    let start = *pos;
+
+    let string_key = if matches!(chars.get(*pos), Some('\'') | Some('"')) {
+        Some(parse_quoted_string(chars, pos))
+    } else {
+        // A bare key candidate (number, constant, etc.) — scan a simple token, not
+        // the broader `skip_bare_token` sweep, so we don't overrun into `=>` itself.
+        while matches!(chars.get(*pos), Some(c) if is_ident_start(*c) || c.is_ascii_digit() || *c == '.' || *c == '-') {
+            *pos += 1;
+        }
+        if *pos == start {
+            return None;
+        }
+        None
+    };
+
+    skip_ws_and_comments(chars, pos);
+    if chars.get(*pos) == Some(&'=') && chars.get(*pos + 1) == Some(&'>') {
+        *pos += 2;
+        Some(string_key)
+    } else {
+        *pos = start;
+        None
+    }
+}
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn matches_keyword(chars: &[char], pos: usize, kw: &str) -> bool {
+    let after_ok = chars.get(pos + kw.len()).is_none_or(|c| !c.is_ascii_alphanumeric() && *c != '_');
+    after_ok && kw.chars().enumerate().all(|(i, c)| chars.get(pos + i) == Some(&c))
+}
+
+fn skip_identifier(chars: &[char], pos: &mut usize) {
+    while matches!(chars.get(*pos), Some(c) if c.is_ascii_alphanumeric() || *c == '_') {
+        *pos += 1;
+    }
+}
+
+/// Whether `c` is part of a PHP infix operator (`.`, `?:`, `??`, arithmetic, etc.)
+/// that can appear between a value and the next array entry.
+fn is_operator_char(c: char) -> bool {
+    matches!(c, '.' | '?' | ':' | '+' | '-' | '*' | '/' | '%' | '!' | '=' | '<' | '>' | '&' | '|' | '^' | '~')
+}
+
+/// Skip a run of operator characters (e.g. `.`, `??`, `?:`, `===`).
+fn skip_operator(chars: &[char], pos: &mut usize) {
+    while matches!(chars.get(*pos), Some(c) if is_operator_char(*c)) {
+        *pos += 1;
+    }
+}
+
+/// Skip a bare (non-string, non-bracketed) token up to the next top-level delimiter.
+fn skip_bare_token(chars: &[char], pos: &mut usize) {
+    while !matches!(chars.get(*pos), None | Some(',') | Some(']') | Some(')')) {
+        *pos += 1;
+    }
+}
+
+/// Skip from an opening `open` at `chars[*pos]` to its matching `close`, stepping over
+/// any nested strings/brackets/parens so they can't be mistaken for the outer pair.
+fn skip_balanced(chars: &[char], pos: &mut usize, open: char, close: char) {
+    if chars.get(*pos) != Some(&open) {
+        return;
+    }
+    *pos += 1;
+    skip_balanced_from_depth(chars, pos, open, close, 1);
+}
+
+/// Like `skip_balanced`, but starting already `depth` levels deep (used right after
+/// the caller has consumed the opening delimiter itself).
+fn skip_balanced_from_depth(chars: &[char], pos: &mut usize, open: char, close: char, mut depth: u32) {
+    while depth > 0 {
+        match chars.get(*pos) {
+            Some('\'') | Some('"') => {
+                parse_quoted_string(chars, pos);
+            }
+            Some(c) if *c == open => {
+                depth += 1;
+                *pos += 1;
+            }
+            Some(c) if *c == close => {
+                depth -= 1;
+                *pos += 1;
+            }
+            Some(_) => {
+                *pos += 1;
+            }
+            None => break,
+        }
+    }
+}
+
+/// Descend into `config_json` following a dotted path (e.g. `database.connections.mysql.host`).
+fn resolve_config_path<'a>(config_json: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut current = config_json;
+    for part in path.split('.') {
+        current = current.get(part)?;
+    }
+    Some(current)
+}
+
+/// Parse `bootstrap/cache/config.php` directly in Rust, with no `php` binary required.
+/// The cache file is always a single `<?php return [ ... ];` array literal containing
+/// only scalars, nested arrays, and stringified objects, so this tokenizes and
+/// evaluates that literal straight into a `serde_json::Value`.
+fn load_config_native(path: &str) -> Value {
+    let content = fs::read_to_string(path).expect("Failed to read config cache file");
+    let body = extract_return_expression(&content);
+    let mut parser = PhpLiteralParser::new(body);
+    parser.parse_value()
+}
+
+/// Strip the `<?php ... return` prefix and trailing `;` from a cached config file,
+/// leaving just the array literal expression.
+///
+/// Looks for a `return` keyword that is actually followed by an array value (after
+/// skipping whitespace/comments), so a `/** @return array */` doc comment ahead of the
+/// real `return [ ... ];` statement can't be mistaken for it.
+fn extract_return_expression(content: &str) -> &str {
+    let chars: Vec<char> = content.chars().collect();
+    let start_char_idx = find_return_array_start(content, &chars);
+
+    if start_char_idx >= chars.len() {
+        return content.trim();
+    }
+
+    let start_byte = content
+        .char_indices()
+        .nth(start_char_idx)
+        .map(|(byte_idx, _)| byte_idx)
+        .unwrap_or(content.len());
+    let end = content.rfind(';').unwrap_or(content.len());
+    content[start_byte..end].trim()
+}
+
+/// A minimal recursive-descent parser for PHP array-literal expressions: strings,
+/// numbers, `true`/`false`/`null`, and nested `[ ... ]` / legacy `array( ... )` arrays
+/// with `'key' => value` or implicit numeric keys. Anything beyond that (closures,
+/// objects, constants) isn't representable here — use `load_config_via_php` instead.
+struct PhpLiteralParser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl PhpLiteralParser {
+    fn new(src: &str) -> Self {
+        PhpLiteralParser { chars: src.chars().collect(), pos: 0 }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    /// Skip whitespace, `//`/`#` line comments, and `/* ... */` block comments.
+    fn skip_ws(&mut self) {
+        loop {
+            while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+                self.pos += 1;
+            }
+            if self.peek() == Some('/') && self.chars.get(self.pos + 1) == Some(&'/') {
+                while !matches!(self.peek(), None | Some('\n')) {
+                    self.pos += 1;
+                }
+                continue;
+            }
+            if self.peek() == Some('#') {
+                while !matches!(self.peek(), None | Some('\n')) {
+                    self.pos += 1;
+                }
+                continue;
+            }
+            if self.peek() == Some('/') && self.chars.get(self.pos + 1) == Some(&'*') {
+                self.pos += 2;
+                while self.pos < self.chars.len()
+                    && !(self.peek() == Some('*') && self.chars.get(self.pos + 1) == Some(&'/'))
+                {
+                    self.pos += 1;
+                }
+                self.pos = (self.pos + 2).min(self.chars.len());
+                continue;
+            }
+            break;
+        }
+    }
+
+    fn matches_keyword(&self, kw: &str) -> bool {
+        let after_ok = self
+            .chars
+            .get(self.pos + kw.len())
+            .is_none_or(|c| !c.is_ascii_alphanumeric() && *c != '_');
+        after_ok && kw.chars().enumerate().all(|(i, c)| self.chars.get(self.pos + i) == Some(&c))
+    }
+
+    fn parse_value(&mut self) -> Value {
+        self.skip_ws();
+        match self.peek() {
+            Some('[') => self.parse_array('[', ']'),
+            Some('\'') => Value::String(self.parse_single_quoted()),
+            Some('"') => Value::String(self.parse_double_quoted()),
+            Some(_) if self.matches_keyword("array") => {
+                self.pos += "array".len();
+                self.skip_ws();
+                if self.peek() == Some('(') {
+                    self.parse_array('(', ')')
+                } else {
+                    // "array" keyword not actually followed by `(` — not a valid
+                    // legacy array literal, so don't recurse into parse_array.
+                    Value::Null
+                }
+            }
+            _ => self.parse_scalar_literal(),
+        }
+    }
+
+    /// Parse an array literal whose opening delimiter (`[` or `(`) is expected at the
+    /// current position. This is a real runtime check rather than a `debug_assert!` —
+    /// release builds parsing an untrusted/malformed cache file must fail safely
+    /// (return `Value::Null`) instead of misreading the rest of the file as if the
+    /// delimiter were there.
+    fn parse_array(&mut self, open: char, close: char) -> Value {
+        if self.peek() != Some(open) {
+            return Value::Null;
+        }
+        self.pos += 1;
+
+        let mut map = serde_json::Map::new();
+        let mut next_index: i64 = 0;
+
+        loop {
+            self.skip_ws();
+            match self.peek() {
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                None => break,
+                _ => {}
+            }
+
+            let first = self.parse_value();
+            self.skip_ws();
+
+            if self.peek() == Some('=') && self.chars.get(self.pos + 1) == Some(&'>') {
+                self.pos += 2;
+                self.skip_ws();
+                map.insert(value_to_key(&first), self.parse_value());
+            } else {
+                map.insert(next_index.to_string(), first);
+                next_index += 1;
+            }
+
+            self.skip_ws();
+            match self.peek() {
+                Some(',') => {
+                    self.pos += 1;
+                }
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                _ => break,
+            }
+        }
+
+        Value::Object(map)
+    }
+
+    fn parse_single_quoted(&mut self) -> String {
+        self.pos += 1;
+        let mut s = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\\' && matches!(self.chars.get(self.pos + 1), Some('\'') | Some('\\')) {
+                s.push(self.chars[self.pos + 1]);
+                self.pos += 2;
+                continue;
+            }
+            self.pos += 1;
+            if c == '\'' {
+                break;
+            }
+            s.push(c);
+        }
+        s
+    }
+
+    fn parse_double_quoted(&mut self) -> String {
+        self.pos += 1;
+        let mut s = String::new();
+        while let Some(c) = self.peek() {
+            if c == '\\' {
+                if let Some(escaped) = self.chars.get(self.pos + 1).and_then(|next| match next {
+                    'n' => Some('\n'),
+                    't' => Some('\t'),
+                    'r' => Some('\r'),
+                    '"' => Some('"'),
+                    '\\' => Some('\\'),
+                    '$' => Some('$'),
+                    _ => None,
+                }) {
+                    s.push(escaped);
+                    self.pos += 2;
+                    continue;
+                }
+            }
+            self.pos += 1;
+            if c == '"' {
+                break;
+            }
+            s.push(c);
+        }
+        s
+    }
+
+    /// Parse `true`/`false`/`null` or a bare number, falling back to an unquoted string.
+    fn parse_scalar_literal(&mut self) -> Value {
+        let start = self.pos;
+        // Stop before `=>` too, so a numeric key (`0 => 'x'`) parses as a number.
+        while !matches!(self.peek(), None | Some(',') | Some(']') | Some(')'))
+            && !(self.peek() == Some('=') && self.chars.get(self.pos + 1) == Some(&'>'))
+        {
+            self.pos += 1;
+        }
+        let raw: String = self.chars[start..self.pos].iter().collect();
+        let raw = raw.trim();
+
+        match raw.to_ascii_lowercase().as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" | "" => Value::Null,
+            _ => raw
+                .parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| raw.parse::<f64>().map(Value::from))
+                .unwrap_or_else(|_| Value::String(raw.to_string())),
+        }
+    }
+}
+
+/// Convert a parsed key value (string or number) back into a JSON object key.
+fn value_to_key(val: &Value) -> String {
+    match val {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        other => json_value_to_string(other),
+    }
 }
 
-/// Load config.php with PHP, convert to JSON and return as Value
-fn load_config_php_as_json(path: &str) -> Value {
+/// Load config.php by shelling out to PHP, convert to JSON and return as Value.
+/// Kept as an opt-in fallback (`--use-php`) for cached configs containing closures
+/// or objects that the native parser in `load_config_native` can't represent.
+fn load_config_via_php(path: &str) -> Value {
     let php_code = format!(
         r#"
         function sanitize($data) {{
@@ -144,6 +1090,7 @@ fn json_value_to_string(val: &Value) -> String {
         Value::String(s) => s.clone(),
         Value::Number(n) => n.to_string(),
         Value::Bool(b) => b.to_string(),
+        Value::Null => "null".to_string(),
         _ => format!("{val:?}"),
     }
 }
@@ -195,6 +1142,253 @@ mod tests {
         assert!(!found_diff, "Expected no differences between .env and config cache");
     }
 
+    /// A sibling key whose `env()` default is itself an array (e.g.
+    /// `'options' => env('DB_OPTIONS', ['timeout' => 5])`) must not corrupt the
+    /// dotted path resolved for neighboring keys.
+    #[test]
+    fn walk_config_env_paths_ignores_brackets_inside_env_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        create_dir_all(&config_dir).unwrap();
+
+        let database_config = r#"<?php
+return [
+    'connections' => [
+        'mysql' => [
+            'host' => env('DB_HOST', '127.0.0.1'),
+            'options' => env('DB_OPTIONS', ['timeout' => 5]),
+            'port' => env('DB_PORT', '3306'),
+        ],
+    ],
+];"#;
+        fs::write(config_dir.join("database.php"), database_config).unwrap();
+
+        let (_, env_key_to_path) = parse_config_env_keys(&config_dir);
+
+        assert_eq!(
+            env_key_to_path.get("DB_HOST").map(String::as_str),
+            Some("database.connections.mysql.host")
+        );
+        assert_eq!(
+            env_key_to_path.get("DB_PORT").map(String::as_str),
+            Some("database.connections.mysql.port")
+        );
+    }
+
+    /// A value followed by a trailing operator (string concatenation, ternary,
+    /// null-coalescing, etc.) must not truncate the rest of the array — every key
+    /// after it still needs to resolve.
+    #[test]
+    fn walk_config_env_paths_survives_trailing_operators() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        create_dir_all(&config_dir).unwrap();
+
+        let app_config = r#"<?php
+return [
+    'url' => env('APP_URL', 'http://localhost') . '/base',
+    'timezone' => env('APP_TIMEZONE') ?: 'UTC',
+    'locale' => env('APP_LOCALE') ?? 'en',
+    'next' => env('NEXT_KEY', 'value'),
+];"#;
+        fs::write(config_dir.join("app.php"), app_config).unwrap();
+
+        let (_, env_key_to_path) = parse_config_env_keys(&config_dir);
+
+        assert_eq!(env_key_to_path.get("APP_URL").map(String::as_str), Some("app.url"));
+        assert_eq!(env_key_to_path.get("APP_TIMEZONE").map(String::as_str), Some("app.timezone"));
+        assert_eq!(env_key_to_path.get("APP_LOCALE").map(String::as_str), Some("app.locale"));
+        assert_eq!(env_key_to_path.get("NEXT_KEY").map(String::as_str), Some("app.next"));
+    }
+
+    /// A non-string array key (numeric index, PHP constant) before an `env()` entry
+    /// must still be skipped correctly, without derailing the entries after it.
+    #[test]
+    fn walk_config_env_paths_skips_non_string_keys() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("config");
+        create_dir_all(&config_dir).unwrap();
+
+        let database_config = r#"<?php
+return [
+    'options' => [
+        0 => env('ZERO_KEY', 'x'),
+        'after' => env('AFTER_KEY', 'y'),
+    ],
+];"#;
+        fs::write(config_dir.join("database.php"), database_config).unwrap();
+
+        let (_, env_key_to_path) = parse_config_env_keys(&config_dir);
+
+        assert_eq!(
+            env_key_to_path.get("AFTER_KEY").map(String::as_str),
+            Some("database.options.after")
+        );
+    }
+
+    /// `load_config_native` must handle legacy `array()` syntax, escaped quotes, and
+    /// arrays nested inside `array()` (not just `[ ]`) — not just the shapes the other
+    /// fixtures happen to exercise.
+    #[test]
+    fn load_config_native_parses_array_syntax_and_escapes() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("config.php");
+
+        let config_cache = r#"<?php
+return array(
+    'name' => 'It\'s "Acme"',
+    'providers' => array(
+        'app' => array(
+            'timeout' => 30,
+            'enabled' => true,
+        ),
+    ),
+);"#;
+        fs::write(&cache_path, config_cache).unwrap();
+
+        let value = load_config_native(&cache_path.to_string_lossy());
+
+        assert_eq!(value.get("name").and_then(Value::as_str), Some("It's \"Acme\""));
+        assert_eq!(
+            value.pointer("/providers/app/timeout").and_then(Value::as_i64),
+            Some(30)
+        );
+        assert_eq!(
+            value.pointer("/providers/app/enabled").and_then(Value::as_bool),
+            Some(true)
+        );
+    }
+
+    /// An array/object `config_value` must serialize as real nested JSON in the
+    /// `--format json` report, not a Rust debug-formatted string.
+    #[test]
+    fn json_report_serializes_array_config_value_as_real_json() {
+        let finding = Finding {
+            key: "DB_OPTIONS".to_string(),
+            kind: FindingKind::Diff,
+            env_value: Some("true".to_string()),
+            config_value: Some(serde_json::json!({ "timeout": 5 })),
+            config_path: Some("database.connections.mysql.options".to_string()),
+            source_file: Some("config/database.php".to_string()),
+        };
+
+        let report = build_json_report(&[finding]);
+        let serialized_config_value = &report["findings"][0]["config_value"];
+
+        assert_eq!(serialized_config_value, &serde_json::json!({ "timeout": 5 }));
+        assert_eq!(serialized_config_value["timeout"].as_i64(), Some(5));
+    }
+
+    /// `--strict` requires exact (case-sensitive) null matches, not a false [DIFF]
+    /// on every correctly-configured nullable key — `json_value_to_string` must
+    /// render `Value::Null` as the literal `"null"`, not `Debug`'s `"Null"`.
+    #[test]
+    fn diff_findings_strict_matches_null_value() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("REDIS_PASSWORD".to_string(), "null".to_string());
+
+        let mut env_key_to_path = HashMap::new();
+        env_key_to_path.insert("REDIS_PASSWORD".to_string(), "database.redis.password".to_string());
+
+        let config_json = serde_json::json!({
+            "database": { "redis": { "password": null } },
+        });
+
+        let findings = diff_findings(&env_vars, &HashMap::new(), &env_key_to_path, &config_json, true);
+
+        assert!(findings.is_empty(), "expected no drift, got {findings:?}");
+    }
+
+    /// Under `--strict`, a `.env` key whose dotted path can't be resolved in the
+    /// cache must be reported distinctly (`PathUnresolved`), not silently folded
+    /// into an ordinary value-mismatch `Diff`.
+    #[test]
+    fn diff_findings_strict_reports_unresolved_path_distinctly() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("DB_HOST".to_string(), "localhost".to_string());
+
+        let mut env_key_to_path = HashMap::new();
+        env_key_to_path.insert("DB_HOST".to_string(), "database.connections.mysql.host".to_string());
+
+        let config_json = serde_json::json!({ "database": {} });
+
+        let findings = diff_findings(&env_vars, &HashMap::new(), &env_key_to_path, &config_json, true);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, FindingKind::PathUnresolved);
+        assert!(format_text_finding(&findings[0]).contains("not found in cache"));
+    }
+
+    /// `--audit` must report a config-referenced key missing from `.env` as
+    /// [UNDEFINED], and an `.env` key no config file reads as [UNUSED] — and not
+    /// flag a key present on both sides.
+    #[test]
+    fn audit_findings_reports_undefined_and_unused_keys() {
+        let mut env_key_to_section = HashMap::new();
+        env_key_to_section.insert("DB_HOST".to_string(), "database".to_string());
+        env_key_to_section.insert("MAIL_HOST".to_string(), "mail".to_string());
+
+        let mut env_vars = HashMap::new();
+        env_vars.insert("DB_HOST".to_string(), "localhost".to_string());
+        env_vars.insert("UNUSED_KEY".to_string(), "leftover".to_string());
+
+        let findings = audit_findings(&env_key_to_section, &env_vars);
+
+        let undefined: Vec<&str> = findings
+            .iter()
+            .filter(|f| f.kind == FindingKind::Undefined)
+            .map(|f| f.key.as_str())
+            .collect();
+        let unused: Vec<&str> = findings
+            .iter()
+            .filter(|f| f.kind == FindingKind::Unused)
+            .map(|f| f.key.as_str())
+            .collect();
+
+        assert_eq!(undefined, vec!["MAIL_HOST"]);
+        assert_eq!(unused, vec!["UNUSED_KEY"]);
+    }
+
+    /// Later `--env` files override earlier ones key-by-key, rather than replacing
+    /// the whole set.
+    #[test]
+    fn load_env_file_keys_values_layers_with_later_files_overriding_earlier() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join(".env");
+        let override_path = temp_dir.path().join(".env.testing");
+
+        fs::write(&base_path, "APP_NAME=Base\nAPP_DEBUG=true\n").unwrap();
+        fs::write(&override_path, "APP_NAME=Testing\n").unwrap();
+
+        let files = vec![
+            (base_path.to_string_lossy().to_string(), true),
+            (override_path.to_string_lossy().to_string(), true),
+        ];
+        let env_vars = load_env_file_keys_values(&files);
+
+        assert_eq!(env_vars.get("APP_NAME").map(String::as_str), Some("Testing"));
+        assert_eq!(env_vars.get("APP_DEBUG").map(String::as_str), Some("true"));
+    }
+
+    /// A missing optional layered file (e.g. an absent `.env.{APP_ENV}`) is skipped
+    /// silently rather than causing an error.
+    #[test]
+    fn load_env_file_keys_values_skips_missing_optional_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let base_path = temp_dir.path().join(".env");
+        let missing_path = temp_dir.path().join(".env.testing");
+
+        fs::write(&base_path, "APP_NAME=Base\n").unwrap();
+
+        let files = vec![
+            (base_path.to_string_lossy().to_string(), true),
+            (missing_path.to_string_lossy().to_string(), false),
+        ];
+        let env_vars = load_env_file_keys_values(&files);
+
+        assert_eq!(env_vars.get("APP_NAME").map(String::as_str), Some("Base"));
+    }
+
     /// Setup for test with differences
     fn setup_test_with_differences() -> (HashMap<String, String>, HashMap<String, String>, Value) {
         let temp_dir = TempDir::new().unwrap();
@@ -290,11 +1484,11 @@ return [
 
     /// Load test data
     fn load_test_data(temp_path: &Path) -> (HashMap<String, String>, HashMap<String, String>, Value) {
-        let env_vars = load_env_file_keys_values(&temp_path.join(".env").to_string_lossy());
+        let env_vars = load_env_file_keys_values(&[(temp_path.join(".env").to_string_lossy().to_string(), true)]);
         let config_dir = temp_path.join("config");
-        let env_key_to_section = parse_config_env_keys(&config_dir);
-        let config_json = load_config_php_as_json(&temp_path.join("bootstrap/cache/config.php").to_string_lossy());
-        
+        let (env_key_to_section, _env_key_to_path) = parse_config_env_keys(&config_dir);
+        let config_json = load_config_native(&temp_path.join("bootstrap/cache/config.php").to_string_lossy());
+
         (env_vars, env_key_to_section, config_json)
     }
 }
\ No newline at end of file